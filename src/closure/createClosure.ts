@@ -61,6 +61,12 @@ export interface FunctionInfo extends ObjectInfo {
   // name into the serialized code for it.
   name: string | undefined;
 
+  // name that V8 inferred for the function (i.e. from the variable or property it was assigned to),
+  // if names should be inferred.  used only for emitting a better name when 'name' is not set.
+  // Uses V8's %FunctionGetInferredName, so covers property assignments as well as the names that
+  // default parameters and class fields get through 'name'.
+  inferredName?: string;
+
   // Number of parameters this function is declared to take.  Used to generate a serialized
   // function with the same number of parameters.  This is valuable as some 3rd party libraries
   // (like senchalabs: https://github.com/senchalabs/connect/blob/fa8916e6350e01262e86ccee82f490c65e04c728/index.js#L232-L241)
@@ -241,6 +247,10 @@ export async function createClosureInfoAsync(
   async function addEntriesForWellKnownGlobalObjectsAsync() {
     const seenGlobalObjects = new Set<any>();
 
+    // Refer to the global object itself as 'global', rather than through whichever of its own
+    // aliases ('global', 'globalThis') the walk below happens to hit first.
+    await addEntriesAsync(global, "global");
+
     // Front load these guys so we prefer emitting code that references them directly,
     // instead of in unexpected ways.  i.e. we'd prefer to have Number.prototype vs
     // Object.getPrototypeOf(Infinity) (even though they're the same thing.)
@@ -267,7 +277,7 @@ export async function createClosureInfoAsync(
 
     // Add information so that we can properly serialize over generators/iterators.
     await addGeneratorEntriesAsync();
-    await addEntriesAsync(Symbol.iterator, "Symbol.iterator");
+    await addWellKnownSymbolEntriesAsync();
 
     return;
 
@@ -286,6 +296,19 @@ export async function createClosureInfoAsync(
       context.cache.set(val, { expr: emitExpr });
     }
 
+    // Well-known symbols (Symbol.iterator, Symbol.asyncIterator, Symbol.toPrimitive, etc.)
+    // are shared by every realm.  Refer to them by name so that methods keyed by them are
+    // installed on the same symbol after deserialization, rather than on a fresh local
+    // symbol with the same description.
+    async function addWellKnownSymbolEntriesAsync() {
+      for (const key of Object.getOwnPropertyNames(Symbol)) {
+        const val = (<any>Symbol)[key];
+        if (typeof val === "symbol") {
+          await addEntriesAsync(val, `Symbol.${key}`);
+        }
+      }
+    }
+
     async function addGlobalInfoAsync(key: string) {
       const globalObj = (<any>global)[key];
       const text = utils.isLegalMemberName(key)
@@ -434,6 +457,7 @@ async function analyzeFunctionInfoAsync(
       env: new Map(),
       usesNonLexicalThis: parsedFunction.usesNonLexicalThis,
       name: functionDeclarationName,
+      inferredName: args.inferNames ? getInferredName(func) : undefined,
       paramCount: func.length,
     };
 
@@ -701,6 +725,13 @@ async function analyzeFunctionInfoAsync(
   }
 }
 
+function getInferredName(func: Function): string | undefined {
+  // V8 infers names like 'obj.handler' for functions assigned to properties.  Only the last part
+  // is meaningful as the name of the serialized function.
+  const name = v8.getFunctionInferredName(func);
+  return name?.slice(name.lastIndexOf(".") + 1);
+}
+
 async function computeIsAsyncFunction(func: Function): Promise<boolean> {
   // Note, i can't think of a better way to determine this.  This is particularly hard because we
   // can't even necessary refer to async function objects here as this code is rewritten by TS,
@@ -1087,6 +1118,21 @@ async function getOrCreateEntryAsync(
       return;
     }
 
+    const handleKind = getProcessHandleKind(obj);
+    if (handleKind) {
+      // Timers, streams and servers wrap resources owned by the current process.
+      // Walking into them only fails somewhere deep inside Node's internals, so
+      // report the capture itself.
+      const frame = [...context.frames]
+        .reverse()
+        .find((f) => f.functionLocation !== undefined)!;
+      throwSerializationError(
+        frame.functionLocation!.func,
+        context,
+        `${handleKind} is a handle to a resource of the current process and cannot be serialized. Create it inside the function instead.`
+      );
+    }
+
     const normalizedModuleName = await findNormalizedModuleNameAsync(obj);
     if (normalizedModuleName) {
       await captureModuleAsync(normalizedModuleName);
@@ -1313,8 +1359,14 @@ async function getOrCreateEntryAsync(
       }
       object.env.set(keyEntry, <any>undefined);
 
-      const objPropValue = await getPropertyAsync(obj, propName);
+      // Don't read accessor properties.  Their getter is serialized along with them and runs when
+      // the deserialized code reads the property, so calling it here would only run its side
+      // effects early.
       const propertyInfo = await getPropertyInfoAsync(obj, propName);
+      const objPropValue =
+        propertyInfo?.get || propertyInfo?.set
+          ? undefined
+          : await getPropertyAsync(obj, propName);
       if (!propertyInfo) {
         if (objPropValue !== undefined) {
           throw new Error(
@@ -1343,6 +1395,16 @@ async function getOrCreateEntryAsync(
         }
         const anyInvoked = propChains.some((x) => x.infos[0].invoked);
 
+        // We didn't read the accessor above, so we can't tell if whatever its getter returns
+        // uses 'this'.  If it's invoked or accessed any further, serialize out the entire object.
+        if (
+          (propertyInfo.get || propertyInfo.set) &&
+          (anyInvoked || propChains.some((chain) => chain.infos.length > 1))
+        ) {
+          object.env.clear();
+          return true;
+        }
+
         // Note: objPropValue can be undefined here.  That's the case where the
         // object does have the property, but the property is just set to the
         // undefined value.
@@ -1502,6 +1564,59 @@ function isDerivedNoCaptureConstructor(func: Function): boolean {
   return false;
 }
 
+let processHandleTypes: [string, object][] | undefined;
+function getProcessHandleKind(obj: any): string | undefined {
+  if (!processHandleTypes) {
+    processHandleTypes = computeProcessHandleTypes();
+  }
+
+  // The prototype of a subclass (i.e. 'class LineStream extends fs.ReadStream') inherits from these
+  // too, but it is not a handle itself.  Only instances are, and they don't own a 'constructor'.
+  if (Object.prototype.hasOwnProperty.call(obj, "constructor")) {
+    return undefined;
+  }
+
+  for (const [kind, prototype] of processHandleTypes) {
+    if (prototype.isPrototypeOf(obj)) {
+      return kind;
+    }
+  }
+
+  return undefined;
+
+  function computeProcessHandleTypes(): [string, object][] {
+    // Timeout and Immediate are not exported by the 'timers' module, so get their
+    // prototypes from instances that are cancelled straight away.
+    const timeout = setTimeout(() => {}, 0);
+    clearTimeout(timeout);
+    const immediate = setImmediate(() => {});
+    clearImmediate(immediate);
+
+    // eslint-disable-next-line @typescript-eslint/no-require-imports
+    const fs = require("fs");
+    // eslint-disable-next-line @typescript-eslint/no-require-imports
+    const net = require("net");
+    // eslint-disable-next-line @typescript-eslint/no-require-imports
+    const asyncHooks = require("async_hooks");
+
+    // http.Server and https.Server derive from net.Server.  AsyncLocalStorage is missing on
+    // older versions of node.
+    const types: [string, object | undefined][] = [
+      ["Timeout", Object.getPrototypeOf(timeout)],
+      ["Immediate", Object.getPrototypeOf(immediate)],
+      ["fs.ReadStream", fs.ReadStream.prototype],
+      ["fs.WriteStream", fs.WriteStream.prototype],
+      ["net.Socket", net.Socket.prototype],
+      ["net.Server", net.Server.prototype],
+      ["AsyncLocalStorage", asyncHooks.AsyncLocalStorage?.prototype],
+    ];
+
+    return <[string, object][]>(
+      types.filter(([, prototype]) => prototype !== undefined)
+    );
+  }
+}
+
 let builtInModules: Promise<Map<any, string>> | undefined;
 function getBuiltInModules(): Promise<Map<any, string>> {
   if (!builtInModules) {
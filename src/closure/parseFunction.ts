@@ -99,6 +99,44 @@ const nodeModuleGlobals: { [key: string]: boolean } = {
   require: true,
 };
 
+// Helpers that TypeScript emits into down-level compiled code (i.e. '__awaiter' for async/await or
+// '__rest' for destructuring).  tslib attaches all of these to the 'global' object when it is loaded,
+// but they are never actually built-in and must be serialized along with the code that uses them.
+const tslibHelpers = new Set([
+  "__extends",
+  "__assign",
+  "__rest",
+  "__decorate",
+  "__param",
+  "__esDecorate",
+  "__runInitializers",
+  "__propKey",
+  "__setFunctionName",
+  "__metadata",
+  "__awaiter",
+  "__generator",
+  "__exportStar",
+  "__createBinding",
+  "__values",
+  "__read",
+  "__spread",
+  "__spreadArrays",
+  "__spreadArray",
+  "__await",
+  "__asyncGenerator",
+  "__asyncDelegator",
+  "__asyncValues",
+  "__makeTemplateObject",
+  "__importStar",
+  "__importDefault",
+  "__classPrivateFieldGet",
+  "__classPrivateFieldSet",
+  "__classPrivateFieldIn",
+  "__addDisposableResource",
+  "__disposeResources",
+  "__rewriteRelativeImportExtension",
+]);
+
 // Gets the text of the provided function (using .toString()) and massages it so that it is a legal
 // function declaration.  Note: this ties us heavily to V8 and its representation for functions.  In
 // particular, it has expectations around how functions/lambdas/methods/generators/constructors etc.
@@ -475,10 +513,18 @@ function computeUsesNonLexicalThis(file: ts.SourceFile): boolean {
         return visitCallExpression(<ts.CallExpression>node);
 
       case ts.SyntaxKind.MethodDeclaration:
+      case ts.SyntaxKind.Constructor:
+      case ts.SyntaxKind.GetAccessor:
+      case ts.SyntaxKind.SetAccessor:
       case ts.SyntaxKind.FunctionDeclaration:
       case ts.SyntaxKind.FunctionExpression:
         return visitBaseFunction(<ts.FunctionLikeDeclarationBase>node);
 
+      case ts.SyntaxKind.PropertyDeclaration:
+        // 'this' inside a class field initializer refers to the instance being constructed, not
+        // to the 'this' of the function we're examining.  Only a computed name can use it.
+        return walk((<ts.PropertyDeclaration>node).name);
+
       // Note: it is intentional that we ignore ArrowFunction.  If we use 'this' inside of it,
       // then that should be considered a use of the non-lexical-this from an outer function.
       // i.e.
@@ -582,13 +628,13 @@ function computeCapturedVariableNames(
   return [result, invokedNames];
 
   function isBuiltIn(ident: string): boolean {
-    // __awaiter and __rest are never considered built-in.  We do this as async/await code will generate
-    // an __awaiter (so we will need it), but some libraries (like tslib) will add this to the 'global'
-    // object.  The same is true for __rest when destructuring.
+    // TypeScript helpers (like __awaiter and __rest) are never considered built-in.  We do this as
+    // down-level code will reference them (so we will need them), but some libraries (like tslib)
+    // will add them to the 'global' object.
     // If we think these are built-in, we won't serialize them, and the functions may not
     // actually be available if the import that caused it to get attached isn't included in the
     // final serialized code.
-    if (ident === "__awaiter" || ident === "__rest") {
+    if (tslibHelpers.has(ident)) {
       return false;
     }
 
@@ -651,7 +697,16 @@ function computeCapturedVariableNames(
       case ts.SyntaxKind.CatchClause:
         return visitCatchClause(<ts.CatchClause>node);
       case ts.SyntaxKind.MethodDeclaration:
-        return visitMethodDeclaration(<ts.MethodDeclaration>node);
+      case ts.SyntaxKind.Constructor:
+      case ts.SyntaxKind.GetAccessor:
+      case ts.SyntaxKind.SetAccessor:
+        return visitMethodDeclaration(<ts.FunctionLikeDeclarationBase>node);
+      case ts.SyntaxKind.PropertyDeclaration:
+        return visitPropertyDeclaration(<ts.PropertyDeclaration>node);
+      case ts.SyntaxKind.ClassDeclaration:
+        return visitClassDeclaration(<ts.ClassDeclaration>node);
+      case ts.SyntaxKind.ClassExpression:
+        return visitClassExpression(<ts.ClassExpression>node);
       case ts.SyntaxKind.MetaProperty:
         // don't walk down an es6 metaproperty (i.e. "new.target").  It doesn't
         // capture anything.
@@ -790,10 +845,52 @@ function computeCapturedVariableNames(
   function visitBlockStatement(node: ts.Block): void {
     // Push new scope, visit all block statements, and then restore the scope.
     scopes.push(new Set());
+
+    // Classes declared in this block are in scope for the entire block, so that closures defined
+    // before the declaration can still refer to them.
+    for (const statement of node.statements) {
+      if (ts.isClassDeclaration(statement) && statement.name) {
+        currentScope().add(statement.name.text);
+      }
+    }
+
     ts.forEachChild(node, walk);
     scopes.pop();
   }
 
+  function visitClassDeclaration(node: ts.ClassDeclaration): void {
+    // A class declaration is block scoped, just like 'let'.  If it is directly inside a block we've
+    // already added it when entering that block, but it may also be inside something like a switch
+    // case clause.
+    if (node.name) {
+      currentScope().add(node.name.text);
+    }
+
+    visitClassLikeDeclaration(node);
+  }
+
+  function visitClassExpression(node: ts.ClassExpression): void {
+    // The name of a class expression is only in scope within the class itself.
+    scopes.push(new Set());
+    if (node.name) {
+      currentScope().add(node.name.text);
+    }
+
+    visitClassLikeDeclaration(node);
+    scopes.pop();
+  }
+
+  function visitClassLikeDeclaration(node: ts.ClassLikeDeclaration): void {
+    // Don't walk down the name of the class.  It declares a variable, it doesn't capture one.
+    for (const clause of node.heritageClauses ?? []) {
+      walk(clause);
+    }
+
+    for (const member of node.members) {
+      walk(member);
+    }
+  }
+
   function visitFunctionDeclarationOrExpression(
     node: ts.FunctionDeclaration | ts.FunctionExpression
   ): void {
@@ -811,6 +908,20 @@ function computeCapturedVariableNames(
     node: ts.FunctionLikeDeclarationBase,
     isArrowFunction: boolean,
     functionName: ts.Identifier | undefined
+  ): void {
+    visitFunctionScope(
+      node.parameters,
+      node.body,
+      isArrowFunction,
+      functionName
+    );
+  }
+
+  function visitFunctionScope(
+    parameters: readonly ts.ParameterDeclaration[],
+    body: ts.Node | undefined,
+    isArrowFunction: boolean,
+    functionName: ts.Identifier | undefined
   ): void {
     // First, push new free vars list, scope, and function vars
     const savedRequired = required;
@@ -834,7 +945,7 @@ function computeCapturedVariableNames(
     }
 
     // The parameters of any function are in scope at the top level of the function.
-    for (const param of node.parameters) {
+    for (const param of parameters) {
       nameWalk(param.name, /*isVar:*/ true);
 
       // Parse default argument expressions
@@ -844,7 +955,7 @@ function computeCapturedVariableNames(
     }
 
     // Next, visit the body underneath this new context.
-    walk(node.body);
+    walk(body);
 
     // Remove any function-scoped variables that we encountered during the walk.
     for (const v of functionVars) {
@@ -917,7 +1028,7 @@ function computeCapturedVariableNames(
     // capture so that we pass 'this' along.
     walk(node.expression);
 
-    invokedNames.add(node.expression.getText());
+    invokedNames.add(skipIndirectCallWrapper(node.expression).getText());
 
     if (ts.isCallExpression(node) && isAwaiterCall(node)) {
       return visitBaseFunction(
@@ -935,8 +1046,8 @@ function computeCapturedVariableNames(
     }
   }
 
-  function visitMethodDeclaration(node: ts.MethodDeclaration): void {
-    if (ts.isComputedPropertyName(node.name)) {
+  function visitMethodDeclaration(node: ts.FunctionLikeDeclarationBase): void {
+    if (node.name && ts.isComputedPropertyName(node.name)) {
       // Don't walk down the 'name' part of the property assignment if it is an identifier. It
       // does not capture any variables.  However, if it is a computed property name, walk it
       // as it may capture variables.
@@ -948,6 +1059,22 @@ function computeCapturedVariableNames(
     visitBaseFunction(node, /*isArrowFunction:*/ false, /*name:*/ undefined);
   }
 
+  function visitPropertyDeclaration(node: ts.PropertyDeclaration): void {
+    if (ts.isComputedPropertyName(node.name)) {
+      // Same as for methods: only a computed name can capture anything.
+      walk(node.name);
+    }
+
+    // A class field initializer is evaluated once per instance with 'this' bound to that
+    // instance, so walk it as if it were the body of a parameterless method.
+    visitFunctionScope(
+      [],
+      node.initializer,
+      /*isArrowFunction:*/ false,
+      /*name:*/ undefined
+    );
+  }
+
   function visitPropertyAssignment(node: ts.PropertyAssignment): void {
     if (ts.isComputedPropertyName(node.name)) {
       // Don't walk down the 'name' part of the property assignment if it is an identifier. It
@@ -1082,3 +1209,21 @@ function isAwaiterCall(node: ts.CallExpression) {
 
   return result;
 }
+
+// Compiled output (i.e. TypeScript's commonjs emit) calls imported functions as
+// `(0, module_1.fn)(...)` so that they are invoked without a 'this'.  Look through that wrapper so
+// that we record what is actually being invoked.
+function skipIndirectCallWrapper(node: ts.Expression): ts.Expression {
+  while (true) {
+    if (ts.isParenthesizedExpression(node)) {
+      node = node.expression;
+    } else if (
+      ts.isBinaryExpression(node) &&
+      node.operatorToken.kind === ts.SyntaxKind.CommaToken
+    ) {
+      node = node.right;
+    } else {
+      return node;
+    }
+  }
+}
@@ -44,6 +44,14 @@ export interface SerializeFunctionArgs {
    * be what is exported.
    */
   isFactoryFunction?: boolean;
+  /**
+   * Name serialized anonymous functions after the name V8 inferred for them (i.e. `fetchUser` for
+   * `const fetchUser = () => ...`, or `handler` for `obj.handler = () => ...`) instead of a generic
+   * `__f0`.  Names are read with the `%FunctionGetInferredName` V8 intrinsic, which relies on the
+   * `--allow-natives-syntax` flag this package enables when it is loaded.  This makes stack traces
+   * from the deserialized code easier to follow.  Defaults to false.
+   */
+  inferNames?: boolean;
 }
 
 /**
@@ -153,8 +161,9 @@ function serializeJavaScriptText(
     // reference it from their own code.
     let functionName = functionInfoToEnvVar.get(functionInfo);
     if (!functionName) {
-      functionName = functionInfo.name
-        ? createEnvVarName(functionInfo.name, /*addIndexAtEnd:*/ false)
+      const name = functionInfo.name ?? functionInfo.inferredName;
+      functionName = name
+        ? createEnvVarName(name, /*addIndexAtEnd:*/ false)
         : createEnvVarName("f", /*addIndexAtEnd:*/ true);
       functionInfoToEnvVar.set(functionInfo, functionName);
 
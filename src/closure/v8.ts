@@ -53,3 +53,35 @@ export const lookupCapturedVariableValueAsync =
  */
 export const getFunctionLocationAsync =
   versionSpecificV8Module.getFunctionLocationAsync;
+
+// The inspector has no equivalent of this intrinsic, so we use it on all versions of node.  The
+// Function constructor is used because V8 intrinsics are not valid JavaScript identifiers; they all
+// begin with '%', and only compile because of the --allow-natives-syntax flag set at the top of
+// this file.  Don't fail if a future V8 removes it, as inferred names only make output nicer.
+const functionGetInferredName: ((func: Function) => string) | undefined =
+  (() => {
+    try {
+      return new Function(
+        "func",
+        "return %FunctionGetInferredName(func);"
+      ) as any;
+    } catch {
+      return undefined;
+    }
+  })();
+
+/**
+ * Given a function, returns the name V8 inferred for it from the code that defined it.  Unlike
+ * `Function.prototype.name` this also covers functions assigned to properties, i.e. 'obj.handler'
+ * for `obj.handler = () => {}`.  Returns `undefined` if no name could be inferred.
+ * @internal
+ */
+export function getFunctionInferredName(func: Function): string | undefined {
+  return functionGetInferredName?.(func) || func.name || undefined;
+}
+
+// This function contains syntax that is not legal TS/JS (i.e. "%Whatever").  As such, we cannot
+// serialize it.  In case it somehow gets captured, just block it from closure serialization.
+if (functionGetInferredName) {
+  (<any>functionGetInferredName).doNotCapture = true;
+}
@@ -0,0 +1,25 @@
+"use strict";
+// Copyright 2016-2018, Pulumi Corporation.  All rights reserved.
+
+const cases = [];
+{
+    const base = 1;
+
+    cases.push({
+        title: "Class fields (js)",
+        // eslint-disable-next-line
+        func: () => {
+            const C = class {
+                x = base;
+                y = this.x + 1;
+                static z = 3;
+            };
+            const c = new C();
+            return c.x + c.y + C.z;
+        },
+        expectResult: 6,
+        snapshot: true
+    });
+}
+
+module.exports.cases = cases;
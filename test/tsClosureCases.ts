@@ -21,14 +21,17 @@ import ts from "typescript";
 import * as semver from "semver";
 import { z } from "./mockpackage/lib";
 
+import { AsyncLocalStorage } from "async_hooks";
 import os from "os";
 import path from "path";
 import fs from "fs";
+import http from "http";
 
 import * as deploymentOnlyModule from "./deploymentOnlyModule";
 
 interface ClosureCase {
   pre?: () => void; // an optional function to run before this case.
+  post?: () => void; // an optional function to run after this case.
   title: string; // a title banner for the test case.
   func?: Function; // the function whose body and closure to serialize.
   factoryFunc?: Function; // the function whose body and closure to serialize (as a factory).
@@ -37,6 +40,7 @@ interface ClosureCase {
   error?: string; // error message we expect to be thrown if we are unable to serialize closure.
   afters?: ClosureCase[]; // an optional list of test cases to run afterwards.
   transformers?: ts.TransformerFactory<ts.Node>[];
+  inferNames?: boolean; // name serialized functions after their V8 inferred names.
   inputArguments?: any[];
   expectResult?: any; // optional value - if defined, then the closure will be invoked with this result expected
   expectThrow?: any; // optional value - if defined, then the closure will be invoked with the exception expected
@@ -297,6 +301,77 @@ Function code:
     });
   }
 
+  {
+    const timer = setInterval(() => {}, 1000);
+    clearInterval(timer);
+
+    cases.push({
+      title: "Capture timer handle",
+      func: () => timer.hasRef(),
+      error: `Error serializing function 'func'
+
+function 'func': captured
+  variable 'timer' which indirectly referenced
+    Timeout is a handle to a resource of the current process and cannot be serialized. Create it inside the function instead.
+
+Function code:
+  () => timer.hasRef()
+`,
+    });
+  }
+
+  {
+    // Never listened, so there is nothing to close.
+    const server = http.createServer();
+
+    cases.push({
+      title: "Capture http server handle",
+      func: () => server.listening,
+      error: `Error serializing function 'func'
+
+function 'func': captured
+  variable 'server' which indirectly referenced
+    net.Server is a handle to a resource of the current process and cannot be serialized. Create it inside the function instead.
+
+Function code:
+  () => server.listening
+`,
+    });
+  }
+
+  {
+    const requestContext = new AsyncLocalStorage<string>();
+
+    cases.push({
+      title: "Capture AsyncLocalStorage",
+      func: () => requestContext.getStore(),
+      error: `Error serializing function 'func'
+
+function 'func': captured
+  variable 'requestContext' which indirectly referenced
+    AsyncLocalStorage is a handle to a resource of the current process and cannot be serialized. Create it inside the function instead.
+
+Function code:
+  () => requestContext.getStore()
+`,
+    });
+  }
+
+  {
+    // Only instances of these types are handles.  A subclass can still be captured, although
+    // serializing one in full means serializing node's own stream implementation.
+    class LineStream extends fs.ReadStream {}
+    (<any>LineStream.prototype).encoding = "utf8";
+
+    cases.push({
+      title: "Capture prototype of a process handle subclass",
+      // @ts-ignore: this is just test code.
+      func: () => LineStream.prototype.encoding,
+      snapshot: true,
+      expectResult: "utf8",
+    });
+  }
+
   cases.push({
     title: "Empty function closure w/ args",
     func: function (x: any, y: any, z: any) {},
@@ -1227,6 +1302,74 @@ Function code:
     });
   }
 
+  cases.push({
+    title: "Test getter/setter in object literal",
+    func: () => {
+      let stored = 0;
+      const o = {
+        get value() {
+          return stored;
+        },
+        set value(v: number) {
+          stored = v + 1;
+        },
+      };
+      o.value = 1;
+      return o.value;
+    },
+    snapshot: true,
+    expectResult: 2,
+  });
+
+  cases.push({
+    title: "Test class declared in function body",
+    func: () => {
+      const make = () => new Point(1, 2);
+      class Point {
+        constructor(public x: number, public y: number) {}
+        sum() {
+          return this.x + this.y;
+        }
+      }
+      return make().sum();
+    },
+    snapshot: true,
+    expectResult: 3,
+  });
+
+  cases.push({
+    title: "Test named class expression referring to itself",
+    func: () => {
+      const Counter = class Self {
+        static create() {
+          return new Self();
+        }
+        value = 1;
+      };
+      return Counter.create().value;
+    },
+    snapshot: true,
+    expectResult: 1,
+  });
+
+  cases.push({
+    title: "Test class expression with constructor and getter",
+    func: () => {
+      const Point = class {
+        private x: number;
+        constructor(x: number) {
+          this.x = x;
+        }
+        get double() {
+          return this.x * 2;
+        }
+      };
+      return new Point(2).double;
+    },
+    snapshot: true,
+    expectResult: 4,
+  });
+
   {
     const methodName = "method name";
     class C {
@@ -1280,6 +1423,21 @@ Function code:
     });
   }
 
+  {
+    class C {
+      [Symbol.toPrimitive]() {
+        return 42;
+      }
+    }
+
+    cases.push({
+      title: "Test well-known symbol other than Symbol.iterator",
+      func: () => +new C(),
+      snapshot: true,
+      expectResult: 42,
+    });
+  }
+
   {
     class D {
       public n: number;
@@ -3177,6 +3335,128 @@ Function code:
     ],
   });
 
+  {
+    // The shape TypeScript emits for calls to imported functions in commonjs modules.
+    const module_1 = {
+      add: (a: number, b: number) => a + b,
+      offset: 1,
+    };
+    cases.push({
+      title: "Indirect call through comma expression",
+      // @ts-ignore: this is just test code.
+      func: (x: number) => (0, module_1.add)(x, module_1.offset),
+      inputArguments: [1],
+      snapshot: true,
+      expectResult: 2,
+    });
+  }
+
+  {
+    const add = Object.assign((a: number, b: number) => a + b, { offset: 1 });
+    cases.push({
+      title: "Indirect call of captured function with properties",
+      // @ts-ignore: this is just test code.
+      func: (x: number) => (0, add)(x, add.offset),
+      inputArguments: [1],
+      snapshot: true,
+      expectResult: 2,
+    });
+  }
+
+  {
+    // Mimic a down-level helper that tslib has also attached to the global object.
+    const __assign = (...sources: any[]) => Object.assign({}, ...sources);
+    cases.push({
+      title: "Capture TypeScript helper that is also defined globally",
+      pre: () => {
+        (<any>global).__assign = () => {
+          throw new Error("global helper should not be used");
+        };
+      },
+      post: () => {
+        delete (<any>global).__assign;
+      },
+      func: () => __assign({ a: 1 }, { b: 2 }),
+      snapshot: true,
+      expectResult: { a: 1, b: 2 },
+    });
+  }
+
+  {
+    const fetchUser = (id: number) => ({ id });
+    cases.push({
+      title: "Infer names of anonymous functions",
+      func: () => fetchUser(1),
+      inferNames: true,
+      expectText: `exports.handler = __func;
+(...)
+function __fetchUser(__0) {
+(...)
+}function __func() {
+  return (function() {
+    let fetchUser = __fetchUser;
+(...)`,
+      expectResult: { id: 1 },
+    });
+  }
+
+  {
+    const handlers: any = {};
+    handlers.onEvent = (e: number) => e + 1;
+    cases.push({
+      title: "Infer names of functions assigned to properties",
+      func: (e: number) => handlers.onEvent(e),
+      inferNames: true,
+      inputArguments: [1],
+      expectText: `exports.handler = __func;
+(...)
+function __onEvent(__0) {
+(...)`,
+      expectResult: 2,
+    });
+  }
+
+  {
+    let reads = 0;
+    const config = {
+      get value() {
+        return ++reads;
+      },
+    };
+    cases.push({
+      title: "Getter on captured object is not evaluated during serialization",
+      func: () => config.value,
+      snapshot: true,
+      expectResult: 1,
+      afters: [
+        {
+          title:
+            "Getter on captured object is not evaluated during serialization (after)",
+          func: () => reads,
+          snapshot: true,
+          expectResult: 0,
+        },
+      ],
+    });
+  }
+
+  {
+    const o = {
+      x: 1,
+      get fn() {
+        return function (this: any) {
+          return this.x;
+        };
+      },
+    };
+    cases.push({
+      title: "Capture all if getter returns a function that uses this",
+      func: () => o.fn(),
+      snapshot: true,
+      expectResult: 1,
+    });
+  }
+
   // Run a bunch of direct checks on async js functions if we're in node 8 or above.
   // We can't do this inline as node6 doesn't understand 'async functions'.  And we
   // can't do this in TS as TS will convert the async-function to be a normal non-async
@@ -3191,6 +3471,11 @@ Function code:
     cases.push(...jsCases.cases);
   }
 
+  if (semver.gte(process.version, "12.0.0")) {
+    const jsCases = require("./jsClosureCases_12");
+    cases.push(...jsCases.cases);
+  }
+
   // Make a callback to keep running tests.
   let remaining = cases;
   while (true) {
@@ -3263,6 +3548,11 @@ Function code:
             }
           }
         } finally {
+          // Run post-actions.
+          if (test.post) {
+            test.post();
+          }
+
           if (test.skip) {
             // swallow failures
             return;
@@ -3280,11 +3570,13 @@ Function code:
     if (test.func) {
       return await serializeFunction(test.func, {
         transformers: test.transformers,
+        inferNames: test.inferNames,
       });
     } else if (test.factoryFunc) {
       return await serializeFunction(test.factoryFunc!, {
         isFactoryFunction: true,
         transformers: test.transformers,
+        inferNames: test.inferNames,
       });
     } else {
       throw new Error("Have to supply [func] or [factoryFunc]!");